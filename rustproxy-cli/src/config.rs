@@ -0,0 +1,293 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use clap::ValueEnum;
+use toml_edit::{DocumentMut, Item, Table, value};
+
+use crate::mirrors;
+
+/// Index protocol to wire `replace-with` up to.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Protocol {
+    /// `sparse+` HTTP index (cargo's default since 1.68).
+    Sparse,
+    /// Plain git index, for mirrors that don't expose a sparse endpoint.
+    Git,
+}
+
+/// Path to cargo's config file, `~/.cargo/config.toml`.
+pub fn config_path() -> PathBuf {
+    dirs::home_dir()
+        .unwrap()
+        .join(".cargo")
+        .join("config.toml") // Recommend using config.toml
+}
+
+/// Predefined proxy services
+pub fn get_predefined_proxies() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("rsproxy", "https://rsproxy.cn/crates.io-index/"),
+        ("ustc", "https://mirrors.ustc.edu.cn/crates.io-index/"),
+        ("tuna", "https://mirrors.tuna.tsinghua.edu.cn/crates.io-index/"),
+        ("aliyun", "https://mirrors.aliyun.com/crates.io-index/"),
+    ]
+}
+
+/// Set proxy configuration
+pub fn set_proxy(proxy: &str, protocol: Protocol) -> io::Result<()> {
+    // Determine the proxy name, URL and (if the mirror defines one) an
+    // explicit sparse index, from the built-in + user-defined catalog.
+    let resolved = mirrors::resolve_or_exit(proxy)?;
+    let (proxy_name, proxy_url, explicit_sparse_url) = (resolved.name, resolved.git, resolved.sparse);
+
+    let config_path = config_path();
+
+    // Ensure the .cargo directory exists
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // Read or create the config file
+    let mut doc = if config_path.exists() {
+        let content = fs::read_to_string(&config_path)?;
+        content.parse::<DocumentMut>().unwrap_or_else(|_| DocumentMut::new())
+    } else {
+        DocumentMut::new()
+    };
+
+    // Backup existing config file
+    if config_path.exists() {
+        let backup_path = backup_config(&config_path)?;
+        println!(
+            "Existing configuration backed up to {}",
+            backup_path.to_string_lossy()
+        );
+    }
+
+    // Remove existing proxy configuration blocks
+    remove_proxy_config(&mut doc);
+
+    // Add new proxy configuration blocks in the desired order
+    add_proxy_config(&mut doc, &proxy_name, &proxy_url, explicit_sparse_url.as_deref(), protocol);
+
+    // Write back the updated config file
+    fs::write(&config_path, doc.to_string())?;
+
+    println!(
+        "Proxy configuration set to {}, config file located at {}",
+        proxy_url,
+        config_path.to_string_lossy()
+    );
+
+    Ok(())
+}
+
+/// Clear proxy configuration
+pub fn clear_proxy() -> io::Result<()> {
+    let config_path = config_path();
+
+    if !config_path.exists() {
+        println!("Configuration file does not exist. Nothing to clear.");
+        return Ok(());
+    }
+
+    // Backup existing config file
+    let backup_path = backup_config(&config_path)?;
+    println!(
+        "Existing configuration backed up to {}",
+        backup_path.to_string_lossy()
+    );
+
+    // Read the config file content
+    let content = fs::read_to_string(&config_path)?;
+    let mut doc = content.parse::<DocumentMut>().unwrap_or_else(|_| DocumentMut::new());
+
+    // Remove proxy-related configuration blocks
+    remove_proxy_config(&mut doc);
+
+    // Write back the updated config file
+    fs::write(&config_path, doc.to_string())?;
+
+    println!("Proxy configuration has been successfully cleared.");
+
+    Ok(())
+}
+
+/// Copy `config_path` to a timestamped backup file, so repeated `set`/`clear`
+/// cycles don't clobber the previous backup.
+fn backup_config(config_path: &Path) -> io::Result<PathBuf> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let mut backup_path = config_path.with_extension(format!("backup-{timestamp}"));
+    let mut n = 1;
+    while backup_path.exists() {
+        backup_path = config_path.with_extension(format!("backup-{timestamp}-{n}"));
+        n += 1;
+    }
+    fs::copy(config_path, &backup_path)?;
+    Ok(backup_path)
+}
+
+/// Name of the mirror currently wired up via `[source.crates-io].replace-with`,
+/// if any. Derived from the value itself (stripping the `-sparse` suffix) so
+/// we don't need to persist extra state to know what to tear down.
+fn active_proxy_name(doc: &DocumentMut) -> Option<String> {
+    let replace_with = doc
+        .as_table()
+        .get("source")?
+        .as_table()?
+        .get("crates-io")?
+        .as_table()?
+        .get("replace-with")?
+        .as_str()?;
+    Some(
+        replace_with
+            .strip_suffix("-sparse")
+            .unwrap_or(replace_with)
+            .to_string(),
+    )
+}
+
+/// Remove proxy-related configuration blocks
+fn remove_proxy_config(doc: &mut DocumentMut) {
+    let active_name = active_proxy_name(doc);
+
+    // Remove `replace-with` from [source.crates-io], and the source entries
+    // it pointed at.
+    if let Some(source_table) = doc.as_table_mut().get_mut("source") {
+        if let Item::Table(source) = source_table {
+            if let Some(crates_io) = source.get_mut("crates-io") {
+                if let Item::Table(crates_io_table) = crates_io {
+                    crates_io_table.remove("replace-with");
+                }
+            }
+
+            if let Some(name) = &active_name {
+                source.remove(name);
+                source.remove(&format!("{name}-sparse"));
+            }
+        }
+    }
+
+    // Remove [registries.<name>]
+    if let Some(name) = &active_name {
+        if let Some(registries_table) = doc.as_table_mut().get_mut("registries") {
+            if let Item::Table(registries) = registries_table {
+                registries.remove(name);
+            }
+        }
+    }
+
+    // Remove [net] git-fetch-with-cli
+    if let Some(net_table) = doc.as_table_mut().get_mut("net") {
+        if let Item::Table(net) = net_table {
+            net.remove("git-fetch-with-cli");
+        }
+    }
+}
+
+/// Derive the scheme+host "base" of a mirror URL, e.g.
+/// `https://rsproxy.cn/crates.io-index/` -> `https://rsproxy.cn`.
+fn mirror_base_url(url: &str) -> &str {
+    let scheme_end = url.find("://").map_or(0, |i| i + 3);
+    let host_end = url[scheme_end..]
+        .find('/')
+        .map_or(url.len(), |i| scheme_end + i);
+    &url[..host_end]
+}
+
+/// Add proxy-related configuration blocks in the desired order
+fn add_proxy_config(
+    doc: &mut DocumentMut,
+    name: &str,
+    proxy_url: &str,
+    explicit_sparse_url: Option<&str>,
+    protocol: Protocol,
+) {
+    let git_url = proxy_url.trim_end_matches('/');
+    let sparse_name = format!("{name}-sparse");
+    let sparse_url = explicit_sparse_url
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("sparse+{}/index/", mirror_base_url(proxy_url)));
+
+    // Under the sparse protocol, crates-io is replaced by a dedicated
+    // `<name>-sparse` source; under git, it points straight at `<name>`.
+    let replace_with = match protocol {
+        Protocol::Sparse => sparse_name.clone(),
+        Protocol::Git => name.to_string(),
+    };
+
+    // Add [source.crates-io], preserving any other keys already there.
+    if let Some(source_table) = doc.as_table_mut().get_mut("source") {
+        if let Item::Table(source) = source_table {
+            if let Some(crates_io) = source.get_mut("crates-io") {
+                if let Item::Table(crates_io_table) = crates_io {
+                    crates_io_table["replace-with"] = value(replace_with.clone());
+                }
+            } else {
+                let mut crates_io_table = Table::new();
+                crates_io_table.insert("replace-with", value(replace_with.clone()));
+                source.insert("crates-io", Item::Table(crates_io_table));
+            }
+        }
+    } else {
+        // Create [source] table if it doesn't exist
+        let mut source_table = Table::new();
+        let mut crates_io_table = Table::new();
+        crates_io_table.insert("replace-with", value(replace_with.clone()));
+        source_table.insert("crates-io", Item::Table(crates_io_table));
+        doc.insert("source", Item::Table(source_table));
+    }
+
+    // Add [source.<name>]
+    if let Some(source_table) = doc.as_table_mut().get_mut("source") {
+        if let Item::Table(source) = source_table {
+            let mut mirror_table = Table::new();
+            mirror_table.insert("registry", value(git_url));
+            source.insert(name, Item::Table(mirror_table));
+        }
+    }
+
+    // Add [source.<name>-sparse], only needed for the sparse protocol
+    if matches!(protocol, Protocol::Sparse) {
+        if let Some(source_table) = doc.as_table_mut().get_mut("source") {
+            if let Item::Table(source) = source_table {
+                let mut mirror_sparse_table = Table::new();
+                mirror_sparse_table.insert("registry", value(sparse_url.clone()));
+                source.insert(&sparse_name, Item::Table(mirror_sparse_table));
+            }
+        }
+    }
+
+    // Add [registries.<name>]
+    if let Some(registries_table) = doc.as_table_mut().get_mut("registries") {
+        if let Item::Table(registries) = registries_table {
+            let mut mirror_registry = Table::new();
+            mirror_registry.insert("index", value(git_url));
+            registries.insert(name, Item::Table(mirror_registry));
+        }
+    } else {
+        // Create [registries] table if it doesn't exist
+        let mut registries_table = Table::new();
+        let mut mirror_registry = Table::new();
+        mirror_registry.insert("index", value(git_url));
+        registries_table.insert(name, Item::Table(mirror_registry));
+        doc.insert("registries", Item::Table(registries_table));
+    }
+
+    // Add [net]
+    if let Some(net_table) = doc.as_table_mut().get_mut("net") {
+        if let Item::Table(net) = net_table {
+            net.insert("git-fetch-with-cli", value(true));
+        }
+    } else {
+        // Create [net] table if it doesn't exist
+        let mut net_table = Table::new();
+        net_table.insert("git-fetch-with-cli", value(true));
+        doc.insert("net", Item::Table(net_table));
+    }
+}
@@ -0,0 +1,41 @@
+use std::io;
+
+use clap::ValueEnum;
+
+use crate::mirrors;
+
+/// Shell syntax to emit the environment variables in.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Shell {
+    /// POSIX `export NAME=value`, suitable for bash/zsh/sh.
+    Posix,
+    /// PowerShell `$env:NAME = "value"`.
+    Powershell,
+}
+
+/// Print the cargo environment variables equivalent to `set-proxy`, without
+/// touching `config.toml`. Variable names follow cargo's own
+/// uppercase-and-underscore convention for config keys.
+pub fn print_env(proxy: &str, shell: Shell) -> io::Result<()> {
+    let mirror = mirrors::resolve_or_exit(proxy)?;
+    let registry_var = format!(
+        "CARGO_REGISTRIES_{}_INDEX",
+        mirror.name.to_uppercase().replace('-', "_")
+    );
+    let git_url = mirror.git.trim_end_matches('/');
+
+    let vars = [
+        ("CARGO_SOURCE_CRATES_IO_REPLACE_WITH", mirror.name.as_str()),
+        (registry_var.as_str(), git_url),
+        ("CARGO_NET_GIT_FETCH_WITH_CLI", "true"),
+    ];
+
+    for (name, val) in vars {
+        match shell {
+            Shell::Posix => println!("export {name}=\"{val}\""),
+            Shell::Powershell => println!("$env:{name} = \"{val}\""),
+        }
+    }
+
+    Ok(())
+}
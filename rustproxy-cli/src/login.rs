@@ -0,0 +1,89 @@
+use std::fs;
+use std::io::{self, BufRead};
+use std::path::PathBuf;
+use toml_edit::{DocumentMut, Item, Table, value};
+
+/// Path to cargo's credentials file, `~/.cargo/credentials.toml`.
+fn credentials_path() -> PathBuf {
+    dirs::home_dir().unwrap().join(".cargo").join("credentials.toml")
+}
+
+fn read_doc(path: &PathBuf) -> io::Result<DocumentMut> {
+    if !path.exists() {
+        return Ok(DocumentMut::new());
+    }
+    let content = fs::read_to_string(path)?;
+    Ok(content.parse::<DocumentMut>().unwrap_or_else(|_| DocumentMut::new()))
+}
+
+/// Store a registry token in `~/.cargo/credentials.toml`, never in
+/// `config.toml` (cargo keeps secrets and config separate).
+pub fn login(registry: &str, token: Option<String>) -> io::Result<()> {
+    let token = match token {
+        Some(token) => token,
+        None => {
+            println!("Paste the token for registry '{registry}':");
+            let mut line = String::new();
+            io::stdin().lock().read_line(&mut line)?;
+            line.trim().to_string()
+        }
+    };
+
+    if token.is_empty() {
+        eprintln!("Error: no token provided.");
+        std::process::exit(1);
+    }
+
+    let path = credentials_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut doc = read_doc(&path)?;
+    if let Some(Item::Table(registries)) = doc.as_table_mut().get_mut("registries") {
+        match registries.get_mut(registry) {
+            Some(Item::Table(entry)) => entry["token"] = value(token),
+            _ => {
+                let mut entry = Table::new();
+                entry.insert("token", value(token));
+                registries.insert(registry, Item::Table(entry));
+            }
+        }
+    } else {
+        let mut registries = Table::new();
+        let mut entry = Table::new();
+        entry.insert("token", value(token));
+        registries.insert(registry, Item::Table(entry));
+        doc.insert("registries", Item::Table(registries));
+    }
+
+    fs::write(&path, doc.to_string())?;
+    println!("Stored token for registry '{registry}' in {}", path.to_string_lossy());
+
+    Ok(())
+}
+
+/// Remove a registry's stored token from `~/.cargo/credentials.toml`.
+pub fn logout(registry: &str) -> io::Result<()> {
+    let path = credentials_path();
+    if !path.exists() {
+        println!("Credentials file does not exist. Nothing to remove.");
+        return Ok(());
+    }
+
+    let mut doc = read_doc(&path)?;
+    let removed = if let Some(Item::Table(registries)) = doc.as_table_mut().get_mut("registries") {
+        registries.remove(registry).is_some()
+    } else {
+        false
+    };
+
+    if removed {
+        fs::write(&path, doc.to_string())?;
+        println!("Removed token for registry '{registry}'.");
+    } else {
+        println!("No stored token for registry '{registry}'.");
+    }
+
+    Ok(())
+}
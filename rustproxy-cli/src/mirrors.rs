@@ -0,0 +1,120 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::config::get_predefined_proxies;
+
+/// A single named mirror, whether built-in or user-defined.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MirrorConf {
+    pub name: String,
+    pub git: String,
+    pub sparse: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Shape of `~/.config/rustproxy/mirrors.toml`: a list of `[[mirror]]` tables.
+#[derive(Debug, Deserialize)]
+struct MirrorsFile {
+    #[serde(default, rename = "mirror")]
+    mirrors: Vec<MirrorConf>,
+}
+
+/// Path to the user-defined mirror catalog.
+fn mirrors_config_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap()
+        .join("rustproxy")
+        .join("mirrors.toml")
+}
+
+/// The mirrors rustproxy ships with, as `MirrorConf`s (sparse index left
+/// unset so it gets synthesized from the git URL, as before).
+fn built_in_mirrors() -> Vec<MirrorConf> {
+    get_predefined_proxies()
+        .into_iter()
+        .map(|(name, git)| MirrorConf {
+            name: name.to_string(),
+            git: git.to_string(),
+            sparse: None,
+            description: None,
+        })
+        .collect()
+}
+
+/// Load the user-defined mirrors from `~/.config/rustproxy/mirrors.toml`, if
+/// it exists. Returns an empty list if the file is absent.
+fn load_user_mirrors() -> io::Result<Vec<MirrorConf>> {
+    let path = mirrors_config_path();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)?;
+    let file: MirrorsFile = toml::from_str(&content)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {e}", path.to_string_lossy())))?;
+    Ok(file.mirrors)
+}
+
+/// All known mirrors: built-ins, with user-defined entries merged in on top
+/// (a user entry with the same name replaces the built-in one).
+pub fn all_mirrors() -> io::Result<Vec<MirrorConf>> {
+    let mut mirrors = built_in_mirrors();
+    for user_mirror in load_user_mirrors()? {
+        match mirrors.iter_mut().find(|m| m.name.eq_ignore_ascii_case(&user_mirror.name)) {
+            Some(existing) => *existing = user_mirror,
+            None => mirrors.push(user_mirror),
+        }
+    }
+    Ok(mirrors)
+}
+
+/// Look up a mirror by name (case-insensitive) among built-ins and
+/// user-defined entries.
+pub fn resolve(name: &str) -> io::Result<Option<MirrorConf>> {
+    Ok(all_mirrors()?
+        .into_iter()
+        .find(|m| m.name.eq_ignore_ascii_case(name)))
+}
+
+/// A mirror name paired with the URL(s) to use for it, after resolving
+/// against the built-in + user-defined catalog (or accepting a raw URL).
+pub struct ResolvedMirror {
+    pub name: String,
+    pub git: String,
+    pub sparse: Option<String>,
+}
+
+/// Resolve `proxy` to a mirror, accepting either a known name or a raw
+/// `http(s)://` URL. Prints the list of available names and exits the
+/// process if `proxy` is neither.
+pub fn resolve_or_exit(proxy: &str) -> io::Result<ResolvedMirror> {
+    if let Some(mirror) = resolve(proxy)? {
+        return Ok(ResolvedMirror {
+            name: mirror.name,
+            git: mirror.git,
+            sparse: mirror.sparse,
+        });
+    }
+
+    if proxy.starts_with("http://") || proxy.starts_with("https://") {
+        return Ok(ResolvedMirror {
+            name: "custom".to_string(),
+            git: proxy.to_string(),
+            sparse: None,
+        });
+    }
+
+    eprintln!("Error: Unknown proxy name or invalid URL.");
+    eprintln!("Available proxy names (built-in + user-defined):");
+    for mirror in all_mirrors().unwrap_or_default() {
+        match &mirror.description {
+            Some(description) => eprintln!("  - {} ({})", mirror.name, description),
+            None => eprintln!("  - {}", mirror.name),
+        }
+    }
+    eprintln!("Or provide a custom URL starting with http:// or https://.");
+    std::process::exit(1);
+}
@@ -0,0 +1,136 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+
+use crate::config::config_path;
+
+/// A `[source.NAME]` table, mirroring cargo's own source-replacement schema.
+struct Source {
+    replace_with: Option<String>,
+    registry: Option<String>,
+}
+
+/// A `[registries.NAME]` table.
+struct AdditionalRegistry {
+    index: String,
+}
+
+/// Print a summary of the active registry/source configuration in
+/// `~/.cargo/config.toml`.
+pub fn show_status() -> io::Result<()> {
+    let config_path = config_path();
+
+    if !config_path.exists() {
+        println!("Configuration file does not exist: {}", config_path.to_string_lossy());
+        return Ok(());
+    }
+
+    let content = fs::read_to_string(&config_path)?;
+    let doc = content
+        .parse::<toml_edit::DocumentMut>()
+        .unwrap_or_else(|_| toml_edit::DocumentMut::new());
+
+    println!("Config file: {}", config_path.to_string_lossy());
+
+    let sources = parse_sources(&doc);
+    let registries = parse_registries(&doc);
+
+    match sources.get("crates-io").and_then(|s| s.replace_with.as_deref()) {
+        Some(replace_with) => {
+            println!("crates-io replaced with: {}", replace_with);
+            match resolve_index(&sources, replace_with) {
+                Some(url) => println!("  resolved index: {}", describe_index(&url)),
+                None => println!("  resolved index: <source '{replace_with}' could not be resolved>"),
+            }
+        }
+        None => println!("crates-io is not replaced (using the default registry)"),
+    }
+
+    if registries.is_empty() {
+        println!("Alternate registries: none");
+    } else {
+        println!("Alternate registries:");
+        for (name, registry) in &registries {
+            println!("  - {} -> {}", name, describe_index(&registry.index));
+        }
+    }
+
+    match doc
+        .get("registry")
+        .and_then(|t| t.as_table())
+        .and_then(|t| t.get("default"))
+        .and_then(|v| v.as_str())
+    {
+        Some(default) => println!("Default registry: {}", default),
+        None => println!("Default registry: crates-io (unset)"),
+    }
+
+    let git_fetch_with_cli = doc
+        .get("net")
+        .and_then(|t| t.as_table())
+        .and_then(|t| t.get("git-fetch-with-cli"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    println!("net.git-fetch-with-cli: {}", git_fetch_with_cli);
+
+    Ok(())
+}
+
+/// Parse every `[source.NAME]` table out of the document.
+fn parse_sources(doc: &toml_edit::DocumentMut) -> BTreeMap<String, Source> {
+    let mut sources = BTreeMap::new();
+    if let Some(source_table) = doc.get("source").and_then(|t| t.as_table()) {
+        for (name, item) in source_table.iter() {
+            if let Some(table) = item.as_table() {
+                sources.insert(
+                    name.to_string(),
+                    Source {
+                        replace_with: table.get("replace-with").and_then(|v| v.as_str()).map(String::from),
+                        registry: table.get("registry").and_then(|v| v.as_str()).map(String::from),
+                    },
+                );
+            }
+        }
+    }
+    sources
+}
+
+/// Parse every `[registries.NAME]` table out of the document.
+fn parse_registries(doc: &toml_edit::DocumentMut) -> BTreeMap<String, AdditionalRegistry> {
+    let mut registries = BTreeMap::new();
+    if let Some(registries_table) = doc.get("registries").and_then(|t| t.as_table()) {
+        for (name, item) in registries_table.iter() {
+            if let Some(table) = item.as_table() {
+                if let Some(index) = table.get("index").and_then(|v| v.as_str()) {
+                    registries.insert(name.to_string(), AdditionalRegistry { index: index.to_string() });
+                }
+            }
+        }
+    }
+    registries
+}
+
+/// Follow a chain of `replace-with` pointers to the final registry URL.
+fn resolve_index(sources: &BTreeMap<String, Source>, name: &str) -> Option<String> {
+    let mut current = name;
+    let mut seen = std::collections::HashSet::new();
+    loop {
+        if !seen.insert(current.to_string()) {
+            return None; // cyclic replace-with chain
+        }
+        let source = sources.get(current)?;
+        match (&source.replace_with, &source.registry) {
+            (Some(next), _) => current = next,
+            (None, Some(registry)) => return Some(registry.clone()),
+            (None, None) => return None,
+        }
+    }
+}
+
+/// Label a registry URL as sparse or git, matching cargo's `sparse+` protocol marker.
+fn describe_index(url: &str) -> String {
+    match url.strip_prefix("sparse+") {
+        Some(rest) => format!("{rest} (sparse)"),
+        None => format!("{url} (git)"),
+    }
+}